@@ -0,0 +1,239 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+use tracing::warn;
+
+use crate::{AppState, startup::Peer};
+
+/// Resolve the real client IP for a redirect, honoring `X-Forwarded-For` / `Forwarded`
+/// only when the server is configured to trust the upstream proxy that set them.
+///
+/// Proxies *append* to these headers, so the left-most entry is whatever the
+/// original (possibly attacker-controlled) client sent, and the right-most entry
+/// is the one our own trusted proxy appended — that's the one we want. Without
+/// `trusted_proxy`, these headers are attacker-controlled on a directly exposed
+/// server, so we always fall back to the socket peer address in that case. A Unix
+/// domain socket has no peer address at all, so that fallback becomes a fixed
+/// placeholder — deployments behind nginx over a socket should set
+/// `TRUSTED_PROXY=true` to get real client IPs from `X-Forwarded-For`.
+pub fn client_ip(headers: &HeaderMap, peer: Peer, trusted_proxy: bool) -> String {
+    if trusted_proxy {
+        if let Some(xff) = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok()) {
+            if let Some(last) = xff.split(',').last() {
+                let last = last.trim();
+                if !last.is_empty() {
+                    return last.to_string();
+                }
+            }
+        }
+
+        if let Some(forwarded) = headers.get("forwarded").and_then(|v| v.to_str().ok()) {
+            if let Some(ip) = parse_forwarded_for(forwarded) {
+                return ip;
+            }
+        }
+    }
+
+    match peer {
+        Peer::Tcp(addr) => addr.ip().to_string(),
+        Peer::Unix => "unix-socket".to_string(),
+    }
+}
+
+/// Pull the `for=` parameter out of the last hop of a `Forwarded` header (RFC 7239)
+/// — hops are comma-separated, each made up of semicolon-separated parameters —
+/// stripping the optional quotes and port that may accompany it. The last hop is
+/// the one our own trusted proxy appended, the same reasoning as `X-Forwarded-For`.
+fn parse_forwarded_for(header: &str) -> Option<String> {
+    let last_hop = header.split(',').last()?;
+    last_hop.split(';').find_map(|part| {
+        let part = part.trim();
+        let rest = part.strip_prefix("for=")?;
+        let rest = rest.trim_matches('"');
+        let ip = rest.split(':').next().unwrap_or(rest);
+        if ip.is_empty() { None } else { Some(ip.to_string()) }
+    })
+}
+
+/// Record a single click in the `link_clicks` table. Called from a `tokio::spawn`ed
+/// task so it never sits on the redirect hot path.
+pub async fn record_click(
+    pool: &PgPool,
+    token: &str,
+    referrer: Option<String>,
+    user_agent: Option<String>,
+    client_ip: String,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO link_clicks (token, clicked_at, referrer, user_agent, client_ip)
+         VALUES ($1, NOW(), $2, $3, $4)",
+    )
+    .bind(token)
+    .bind(referrer)
+    .bind(user_agent)
+    .bind(client_ip)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClicksPerDay {
+    pub day: chrono::NaiveDate,
+    pub clicks: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferrerCount {
+    pub referrer: String,
+    pub clicks: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkStats {
+    pub token: String,
+    pub total_clicks: i64,
+    pub clicks_per_day: Vec<ClicksPerDay>,
+    pub top_referrers: Vec<ReferrerCount>,
+}
+
+async fn compute_stats(pool: &PgPool, token: &str) -> anyhow::Result<LinkStats> {
+    let total_clicks: i64 = sqlx::query("SELECT COUNT(*) AS count FROM link_clicks WHERE token = $1")
+        .bind(token)
+        .fetch_one(pool)
+        .await?
+        .get("count");
+
+    let clicks_per_day = sqlx::query(
+        "SELECT clicked_at::date AS day, COUNT(*) AS clicks
+         FROM link_clicks
+         WHERE token = $1
+         GROUP BY day
+         ORDER BY day DESC
+         LIMIT 30",
+    )
+    .bind(token)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| ClicksPerDay {
+        day: row.get("day"),
+        clicks: row.get("clicks"),
+    })
+    .collect();
+
+    let top_referrers = sqlx::query(
+        "SELECT COALESCE(referrer, 'direct') AS referrer, COUNT(*) AS clicks
+         FROM link_clicks
+         WHERE token = $1
+         GROUP BY referrer
+         ORDER BY clicks DESC
+         LIMIT 10",
+    )
+    .bind(token)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| ReferrerCount {
+        referrer: row.get("referrer"),
+        clicks: row.get("clicks"),
+    })
+    .collect();
+
+    Ok(LinkStats {
+        token: token.to_string(),
+        total_clicks,
+        clicks_per_day,
+        top_referrers,
+    })
+}
+
+/// `GET /:token/stats` — aggregate click counts for a link, cached briefly so a
+/// dashboard polling this endpoint doesn't hammer `link_clicks` with the same query.
+pub async fn get_stats(State(state): State<AppState>, Path(token): Path<String>) -> impl IntoResponse {
+    let Some(pool) = &state.analytics_db else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Click analytics require the postgres store backend",
+        )
+            .into_response();
+    };
+
+    if let Some(stats) = state.stats_cache.get(&token).await {
+        return axum::Json(stats).into_response();
+    }
+
+    match compute_stats(pool, &token).await {
+        Ok(stats) => {
+            state.stats_cache.insert(token.clone(), stats.clone()).await;
+            axum::Json(stats).into_response()
+        }
+        Err(e) => {
+            warn!("Failed to compute stats for {}: {}", token, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to compute stats").into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    fn headers_with(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(
+                axum::http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+        headers
+    }
+
+    fn tcp_peer() -> Peer {
+        Peer::Tcp("203.0.113.9:1234".parse::<SocketAddr>().unwrap())
+    }
+
+    #[test]
+    fn xff_picks_the_right_most_hop_when_trusted() {
+        let headers = headers_with(&[("x-forwarded-for", "1.2.3.4, 10.0.0.1")]);
+        assert_eq!(client_ip(&headers, tcp_peer(), true), "10.0.0.1");
+    }
+
+    #[test]
+    fn xff_is_ignored_when_the_proxy_is_not_trusted() {
+        let headers = headers_with(&[("x-forwarded-for", "1.2.3.4, 10.0.0.1")]);
+        assert_eq!(client_ip(&headers, tcp_peer(), false), "203.0.113.9");
+    }
+
+    #[test]
+    fn forwarded_header_picks_the_right_most_hop() {
+        let headers = headers_with(&[(
+            "forwarded",
+            "for=1.2.3.4;proto=https, for=\"10.0.0.1:9999\";proto=http",
+        )]);
+        assert_eq!(client_ip(&headers, tcp_peer(), true), "10.0.0.1");
+    }
+
+    #[test]
+    fn falls_back_to_the_peer_address_without_headers() {
+        let headers = HeaderMap::new();
+        assert_eq!(client_ip(&headers, tcp_peer(), true), "203.0.113.9");
+        assert_eq!(client_ip(&headers, Peer::Unix, true), "unix-socket");
+    }
+
+    #[test]
+    fn parse_forwarded_for_strips_quotes_and_port() {
+        assert_eq!(
+            parse_forwarded_for("for=\"198.51.100.2:8080\""),
+            Some("198.51.100.2".to_string())
+        );
+        assert_eq!(parse_forwarded_for("proto=https"), None);
+    }
+}
@@ -0,0 +1,251 @@
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use axum::{
+    Json,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{Request, StatusCode, header},
+    middleware::Next,
+    response::IntoResponse,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use tracing::warn;
+
+use crate::AppState;
+
+const TOKEN_TTL_SECS: i64 = 3600;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginResponse {
+    pub token: String,
+    pub expires_in: i64,
+}
+
+/// `POST /api/login` — verify the admin credential and issue a short-lived HS256
+/// JWT for the admin API. The password is checked against an Argon2 hash rather
+/// than a plaintext secret.
+pub async fn login(State(state): State<AppState>, Json(req): Json<LoginRequest>) -> impl IntoResponse {
+    if req.username != state.admin_username {
+        return (StatusCode::UNAUTHORIZED, "Invalid credentials").into_response();
+    }
+
+    let parsed_hash = match PasswordHash::new(&state.admin_password_hash) {
+        Ok(hash) => hash,
+        Err(_) => {
+            warn!("ADMIN_PASSWORD_HASH is not a valid Argon2 hash; admin login is disabled");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Server misconfigured").into_response();
+        }
+    };
+
+    if Argon2::default()
+        .verify_password(req.password.as_bytes(), &parsed_hash)
+        .is_err()
+    {
+        return (StatusCode::UNAUTHORIZED, "Invalid credentials").into_response();
+    }
+
+    let exp = (chrono::Utc::now() + chrono::Duration::seconds(TOKEN_TTL_SECS)).timestamp() as usize;
+    let claims = Claims { sub: req.username, exp };
+
+    match encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
+    ) {
+        Ok(token) => Json(LoginResponse {
+            token,
+            expires_in: TOKEN_TTL_SECS,
+        })
+        .into_response(),
+        Err(e) => {
+            warn!("Failed to issue JWT: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to issue token").into_response()
+        }
+    }
+}
+
+/// Middleware gating the admin API behind a valid `Authorization: Bearer <jwt>` header.
+pub async fn require_admin(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> impl IntoResponse {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    let Some(token) = token else {
+        return (StatusCode::UNAUTHORIZED, "Missing bearer token").into_response();
+    };
+
+    let decoded = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(state.jwt_secret.as_bytes()),
+        &Validation::new(Algorithm::HS256),
+    );
+
+    if decoded.is_err() {
+        return (StatusCode::UNAUTHORIZED, "Invalid or expired token").into_response();
+    }
+
+    next.run(req).await.into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListLinksQuery {
+    page: Option<i64>,
+    per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLinkSummary {
+    pub token: String,
+    pub long_url: String,
+    pub is_active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub click_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListLinksResponse {
+    pub links: Vec<AdminLinkSummary>,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+/// `GET /api/links` — paginated list of links with their click counts.
+pub async fn list_links(State(state): State<AppState>, Query(query): Query<ListLinksQuery>) -> impl IntoResponse {
+    let Some(pool) = &state.analytics_db else {
+        return admin_requires_postgres();
+    };
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(50).clamp(1, 200);
+    let offset = (page - 1) * per_page;
+
+    let rows = sqlx::query(
+        "SELECT token, long_url, is_active, created_at, click_count
+         FROM short_links
+         ORDER BY created_at DESC
+         LIMIT $1 OFFSET $2",
+    )
+    .bind(per_page)
+    .bind(offset)
+    .fetch_all(pool)
+    .await;
+
+    match rows {
+        Ok(rows) => {
+            let links = rows
+                .into_iter()
+                .map(|row| AdminLinkSummary {
+                    token: row.get("token"),
+                    long_url: row.get("long_url"),
+                    is_active: row.get("is_active"),
+                    created_at: row.get("created_at"),
+                    click_count: row.get("click_count"),
+                })
+                .collect();
+
+            Json(ListLinksResponse { links, page, per_page }).into_response()
+        }
+        Err(e) => {
+            warn!("Failed to list links: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to list links").into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLinkRequest {
+    pub is_active: Option<bool>,
+    pub long_url: Option<String>,
+}
+
+/// `PATCH /api/links/:token` — toggle `is_active` and/or change `long_url`.
+pub async fn update_link(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    Json(req): Json<UpdateLinkRequest>,
+) -> impl IntoResponse {
+    let Some(pool) = &state.analytics_db else {
+        return admin_requires_postgres();
+    };
+
+    if req.is_active.is_none() && req.long_url.is_none() {
+        return (StatusCode::BAD_REQUEST, "Nothing to update").into_response();
+    }
+
+    let result = sqlx::query(
+        "UPDATE short_links
+         SET is_active = COALESCE($1, is_active),
+             long_url = COALESCE($2, long_url),
+             updated_at = NOW()
+         WHERE token = $3",
+    )
+    .bind(req.is_active)
+    .bind(&req.long_url)
+    .bind(&token)
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(result) if result.rows_affected() == 0 => (StatusCode::NOT_FOUND, "Link not found").into_response(),
+        Ok(_) => {
+            // Evict immediately rather than waiting out the cache's 5 minute TTL.
+            state.link_cache.invalidate(&token).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => {
+            warn!("Failed to update link {}: {}", token, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to update link").into_response()
+        }
+    }
+}
+
+/// `DELETE /api/links/:token`
+pub async fn delete_link(State(state): State<AppState>, Path(token): Path<String>) -> impl IntoResponse {
+    let Some(pool) = &state.analytics_db else {
+        return admin_requires_postgres();
+    };
+
+    let result = sqlx::query("DELETE FROM short_links WHERE token = $1")
+        .bind(&token)
+        .execute(pool)
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() == 0 => (StatusCode::NOT_FOUND, "Link not found").into_response(),
+        Ok(_) => {
+            state.link_cache.invalidate(&token).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => {
+            warn!("Failed to delete link {}: {}", token, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete link").into_response()
+        }
+    }
+}
+
+fn admin_requires_postgres() -> axum::response::Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        "The admin API requires the postgres store backend",
+    )
+        .into_response()
+}
@@ -0,0 +1,110 @@
+use std::env;
+
+/// Which `LinkStore` implementation backs link lookups, selected via `STORE_BACKEND`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreBackend {
+    Postgres,
+    Redis,
+    Memory,
+}
+
+impl StoreBackend {
+    fn from_env(value: Option<String>) -> Self {
+        match value.as_deref().map(str::to_lowercase).as_deref() {
+            None => Self::Postgres,
+            Some("postgres") => Self::Postgres,
+            Some("redis") => Self::Redis,
+            Some("memory") => Self::Memory,
+            Some(other) => {
+                tracing::warn!(
+                    "Unrecognized STORE_BACKEND {:?}; falling back to postgres",
+                    other
+                );
+                Self::Postgres
+            }
+        }
+    }
+}
+
+/// Server configuration loaded from environment variables (and `.env` via dotenvy).
+pub struct Config {
+    pub database_url: String,
+    pub default_redirect: String,
+    pub bind_address: String,
+    pub public_base_url: String,
+    pub shortlink_alphabet: Option<String>,
+    pub shortlink_min_length: u8,
+    pub trusted_proxy: bool,
+    pub store_backend: StoreBackend,
+    pub redis_url: String,
+    pub csv_reload_interval_secs: u64,
+    pub jwt_secret: String,
+    pub admin_username: String,
+    pub admin_password_hash: String,
+}
+
+impl Config {
+    pub fn from_env() -> anyhow::Result<Self> {
+        let database_url = env::var("DATABASE_URL")
+            .unwrap_or_else(|_| "postgresql://localhost/starpaste".to_string());
+
+        let default_redirect = env::var("DEFAULT_REDIRECT_URL")
+            .unwrap_or_else(|_| "https://starpaste.eu".to_string());
+
+        let bind_address = env::var("BIND_ADDRESS").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
+
+        let public_base_url =
+            env::var("PUBLIC_BASE_URL").unwrap_or_else(|_| format!("http://{}", bind_address));
+
+        let shortlink_alphabet = env::var("SHORTLINK_ALPHABET").ok();
+
+        let shortlink_min_length = env::var("SHORTLINK_MIN_LENGTH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        let trusted_proxy = env::var("TRUSTED_PROXY")
+            .ok()
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        let store_backend = StoreBackend::from_env(env::var("STORE_BACKEND").ok());
+
+        let redis_url = env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+
+        let csv_reload_interval_secs = env::var("CSV_RELOAD_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+
+        // Unlike ADMIN_PASSWORD_HASH (which just disables login), a missing
+        // JWT_SECRET can't fall back to a safe default: any hardcoded literal lives
+        // in this public repo's source, so anyone could forge admin tokens signed
+        // with it. Refuse to start instead.
+        let jwt_secret = env::var("JWT_SECRET")
+            .map_err(|_| anyhow::anyhow!("JWT_SECRET must be set; refusing to start with a public, guessable secret"))?;
+
+        let admin_username = env::var("ADMIN_USERNAME").unwrap_or_else(|_| "admin".to_string());
+
+        let admin_password_hash = env::var("ADMIN_PASSWORD_HASH").unwrap_or_else(|_| {
+            tracing::warn!("ADMIN_PASSWORD_HASH is not set; admin login will always fail");
+            String::new()
+        });
+
+        Ok(Self {
+            database_url,
+            default_redirect,
+            bind_address,
+            public_base_url,
+            shortlink_alphabet,
+            shortlink_min_length,
+            trusted_proxy,
+            store_backend,
+            redis_url,
+            csv_reload_interval_secs,
+            jwt_secret,
+            admin_username,
+            admin_password_hash,
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+
+use arc_swap::ArcSwap;
+use moka::future::Cache;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::{ShortLink, metrics::Metrics, read_csv_links};
+
+const CSV_PATH: &str = "links.csv";
+
+/// Spawn the background tasks that keep `csv_links` fresh: a cron-style refresh on
+/// `reload_interval`, and an immediate reload whenever the filesystem watcher sees
+/// `links.csv` change.
+pub fn spawn(
+    csv_links: Arc<ArcSwap<HashMap<String, String>>>,
+    link_cache: Cache<String, ShortLink>,
+    metrics: Metrics,
+    reload_interval: Duration,
+) {
+    {
+        let csv_links = csv_links.clone();
+        let link_cache = link_cache.clone();
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(reload_interval);
+            ticker.tick().await; // first tick fires immediately; links are already loaded at startup
+            loop {
+                ticker.tick().await;
+                reload(&csv_links, &link_cache, &metrics).await;
+            }
+        });
+    }
+
+    tokio::spawn(async move {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let mut watcher = match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        }) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("Failed to create CSV file watcher: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = watcher.watch(Path::new(CSV_PATH), RecursiveMode::NonRecursive) {
+            warn!("Failed to watch {} for changes: {}", CSV_PATH, e);
+            return;
+        }
+
+        info!("Watching {} for changes", CSV_PATH);
+
+        while rx.recv().await.is_some() {
+            reload(&csv_links, &link_cache, &metrics).await;
+        }
+    });
+}
+
+/// Parse a fresh copy of `links.csv`, swap it in atomically, and invalidate any
+/// cached entries whose target changed. `read_csv_links` skips rows it cannot
+/// parse and the remaining rows are swapped in; only a reload that comes back
+/// entirely empty while the previous map had entries is rejected, keeping the
+/// previous map so a bad edit never empties the table.
+async fn reload(
+    csv_links: &Arc<ArcSwap<HashMap<String, String>>>,
+    link_cache: &Cache<String, ShortLink>,
+    metrics: &Metrics,
+) {
+    let previous = csv_links.load_full();
+    let fresh = match tokio::task::spawn_blocking(read_csv_links).await {
+        Ok(links) => links,
+        Err(e) => {
+            warn!("CSV reload task panicked: {}", e);
+            return;
+        }
+    };
+
+    if fresh.is_empty() && !previous.is_empty() {
+        warn!(
+            "Reloaded {} came back empty; keeping the previous {} links",
+            CSV_PATH,
+            previous.len()
+        );
+        return;
+    }
+
+    let added = fresh.keys().filter(|token| !previous.contains_key(*token)).count();
+    let removed: Vec<&String> = previous.keys().filter(|token| !fresh.contains_key(*token)).collect();
+    let changed: Vec<&String> = fresh
+        .iter()
+        .filter(|(token, long_url)| previous.get(*token).is_some_and(|old| old != *long_url))
+        .map(|(token, _)| token)
+        .collect();
+
+    if added == 0 && removed.is_empty() && changed.is_empty() {
+        return;
+    }
+
+    // A token dropped from the CSV entirely needs evicting too — otherwise it
+    // keeps serving its old target from cache until the TTL expires on its own.
+    for token in removed.iter().chain(&changed) {
+        link_cache.invalidate(*token).await;
+    }
+
+    metrics.csv_links.set(fresh.len() as i64);
+    csv_links.store(Arc::new(fresh));
+
+    info!(
+        "Reloaded {}: {} added, {} removed, {} changed",
+        CSV_PATH,
+        added,
+        removed.len(),
+        changed.len()
+    );
+}
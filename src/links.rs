@@ -0,0 +1,52 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLinkRequest {
+    pub long_url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateLinkResponse {
+    pub token: String,
+    pub short_url: String,
+    pub long_url: String,
+}
+
+/// `POST /api/links` — insert a new short link and derive its token from the
+/// link's sequential id via Sqids, so creation never needs a uniqueness retry loop.
+pub async fn create_link(
+    State(state): State<AppState>,
+    Json(req): Json<CreateLinkRequest>,
+) -> impl IntoResponse {
+    if req.long_url.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "long_url must not be empty").into_response();
+    }
+
+    let link = match state.store.create(&req.long_url).await {
+        Ok(link) => link,
+        Err(e) => {
+            tracing::warn!("Failed to create short link: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create short link")
+                .into_response();
+        }
+    };
+
+    state.link_cache.insert(link.token.clone(), link.clone()).await;
+
+    let short_url = format!("{}/{}", state.public_base_url.trim_end_matches('/'), link.token);
+    info!("Created short link {} -> {}", link.token, link.long_url);
+
+    (
+        StatusCode::CREATED,
+        Json(CreateLinkResponse {
+            token: link.token,
+            short_url,
+            long_url: link.long_url,
+        }),
+    )
+        .into_response()
+}
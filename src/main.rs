@@ -16,26 +16,53 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use arc_swap::ArcSwap;
 use axum::{
     Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Redirect},
-    routing::get,
+    routing::{get, patch, post},
 };
 use moka::future::Cache;
 use serde::{Deserialize, Serialize};
-use sqlx::{PgPool, Row};
-use std::{collections::HashMap, env, fs::File, path::Path as StdPath, sync::Arc, time::Duration};
+use sqids::Sqids;
+use sqlx::PgPool;
+use std::{collections::HashMap, fs::File, path::Path as StdPath, sync::Arc, time::Duration};
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{info, warn};
 
+mod analytics;
+mod auth;
+mod config;
+mod csv_watch;
+mod links;
+mod metrics;
+mod startup;
+mod store;
+mod tokens;
+
+use analytics::LinkStats;
+use config::{Config, StoreBackend};
+use metrics::Metrics;
+use startup::Peer;
+use store::{LinkStore, composite::CompositeStore, csv::CsvStore, memory::MemoryStore, postgres::PostgresStore};
+
 #[derive(Clone)]
 struct AppState {
-    db: PgPool,
+    store: Arc<dyn LinkStore>,
+    analytics_db: Option<PgPool>,
     default_redirect: String,
-    csv_links: Arc<HashMap<String, String>>,
+    csv_links: Arc<ArcSwap<HashMap<String, String>>>,
     link_cache: Cache<String, ShortLink>,
+    sqids: Arc<Sqids>,
+    public_base_url: String,
+    trusted_proxy: bool,
+    stats_cache: Cache<String, LinkStats>,
+    metrics: Metrics,
+    jwt_secret: String,
+    admin_username: String,
+    admin_password_hash: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +84,15 @@ enum LinkSource {
     Csv,
 }
 
+impl LinkSource {
+    fn as_label(&self) -> &'static str {
+        match self {
+            LinkSource::Database => "database",
+            LinkSource::Csv => "csv",
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Load .env file
@@ -66,35 +102,50 @@ async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
 
     // Load configuration from environment
-    let database_url = env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "postgresql://localhost/starpaste".to_string());
-
-    let default_redirect =
-       env::var("DEFAULT_REDIRECT_URL").unwrap_or_else(|_| "https://starpaste.eu".to_string());
-
-    let bind_address = env::var("BIND_ADDRESS").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
+    let config = Config::from_env()?;
     info!("Starting short link server...");
-    info!("Database URL: {}", database_url);
-    info!("Default redirect: {}", default_redirect);
-    info!("Bind address: {}", bind_address);
-
-    // Connect to database
-    info!("Attempting to connect to database...");
-    let pool = match PgPool::connect(&database_url).await {
-        Ok(pool) => {
-            info!("Successfully connected to database");
-            pool
-        }
-        Err(e) => {
-            warn!("Failed to connect to database: {}", e);
-            warn!("Make sure PostgreSQL is running and accessible at: {}", database_url);
-            return Err(e.into());
+    info!("Database URL: {}", config.database_url);
+    info!("Default redirect: {}", config.default_redirect);
+    info!("Bind address: {}", config.bind_address);
+
+    let sqids = Arc::new(tokens::build_sqids(
+        config.shortlink_alphabet.clone(),
+        config.shortlink_min_length,
+    )?);
+
+    // Only the Postgres backend connects to a database up front. Click analytics
+    // and the admin API reuse this pool, so they are unavailable on other backends.
+    let pg_pool = if config.store_backend == StoreBackend::Postgres {
+        info!("Attempting to connect to database...");
+        Some(startup::connect_with_retry(&config.database_url).await?)
+    } else {
+        None
+    };
+
+    // Load CSV links at startup; held behind an ArcSwap so the background
+    // watcher can hot-swap a freshly parsed map without a restart.
+    let csv_links = Arc::new(ArcSwap::from_pointee(read_csv_links()));
+    info!("Loaded {} links from CSV file into memory", csv_links.load().len());
+
+    info!("Using {:?} store backend", config.store_backend);
+
+    let primary_store: Arc<dyn LinkStore> = match config.store_backend {
+        StoreBackend::Postgres => Arc::new(PostgresStore::new(
+            pg_pool.clone().expect("postgres pool is set for the postgres backend"),
+            sqids.clone(),
+        )),
+        StoreBackend::Redis => {
+            info!("Connecting to Redis at {}", config.redis_url);
+            Arc::new(store::redis::RedisStore::connect(&config.redis_url, sqids.clone()).await?)
         }
+        StoreBackend::Memory => Arc::new(MemoryStore::new(sqids.clone())),
     };
 
-    // Load CSV links at startup
-    let csv_links = Arc::new(read_csv_links());
-    info!("Loaded {} links from CSV file into memory", csv_links.len());
+    let link_store: Arc<dyn LinkStore> =
+        Arc::new(CompositeStore::new(primary_store, Arc::new(CsvStore::new(csv_links.clone()))));
+
+    let metrics = Metrics::new()?;
+    metrics.csv_links.set(csv_links.load().len() as i64);
 
     // Create a cache for database lookups with 5 minute TTL and max 10000 entries
     let link_cache: Cache<String, ShortLink> = Cache::builder()
@@ -102,26 +153,58 @@ async fn main() -> anyhow::Result<()> {
         .time_to_live(Duration::from_secs(300))
         .build();
 
+    // Stats are expensive grouped queries; cache briefly so a dashboard polling
+    // this endpoint doesn't hammer `link_clicks` with the same query.
+    let stats_cache: Cache<String, LinkStats> = Cache::builder()
+        .max_capacity(1_000)
+        .time_to_live(Duration::from_secs(30))
+        .build();
+
+    csv_watch::spawn(
+        csv_links.clone(),
+        link_cache.clone(),
+        metrics.clone(),
+        Duration::from_secs(config.csv_reload_interval_secs),
+    );
+
     let state = AppState {
-        db: pool,
-        default_redirect,
+        store: link_store,
+        analytics_db: pg_pool,
+        default_redirect: config.default_redirect,
         csv_links,
         link_cache,
+        sqids,
+        public_base_url: config.public_base_url,
+        trusted_proxy: config.trusted_proxy,
+        stats_cache,
+        metrics,
+        jwt_secret: config.jwt_secret,
+        admin_username: config.admin_username,
+        admin_password_hash: config.admin_password_hash,
     };
 
+    // Admin routes are gated behind a bearer JWT; everything else is public.
+    // Link creation lives here too — it's the one endpoint that actually controls
+    // what's in the table, so it can't be left open to anonymous callers.
+    let admin_routes = Router::new()
+        .route("/api/links", get(auth::list_links).post(links::create_link))
+        .route("/api/links/:token", patch(auth::update_link).delete(auth::delete_link))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth::require_admin));
+
     // Build router
     let app = Router::new()
         .route("/", get(handle_root))
+        .route("/api/login", post(auth::login))
         .route("/:token", get(handle_redirect))
+        .route("/:token/stats", get(analytics::get_stats))
+        .route("/metrics", get(metrics::metrics_handler))
         .route("/health", get(health_check))
+        .merge(admin_routes)
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
-    info!("Server starting on {}", bind_address);
-
-    let listener = tokio::net::TcpListener::bind(&bind_address).await?;
-    axum::serve(listener, app).await?;
+    startup::bind_and_serve(&config.bind_address, app).await?;
 
     Ok(())
 }
@@ -134,57 +217,100 @@ async fn handle_root(State(state): State<AppState>) -> impl IntoResponse {
 async fn handle_redirect(
     Path(token): Path<String>,
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<Peer>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     // First, check the in-memory cache
     if let Some(link) = state.link_cache.get(&token).await {
-        // Increment click count asynchronously only for database entries
-        if link.source == LinkSource::Database {
-            let db_clone = state.db.clone();
-            let token_clone = token.clone();
-            tokio::spawn(async move {
-                if let Err(e) = increment_click_count(&db_clone, &token_clone).await {
-                    warn!("Failed to increment click count for {}: {}", token_clone, e);
-                }
-            });
-        }
+        state.metrics.cache_total.with_label_values(&["hit"]).inc();
+        state
+            .metrics
+            .redirects_total
+            .with_label_values(&[link.source.as_label(), "hit"])
+            .inc();
+
+        track_click(&state, &token, link.source, &headers, peer);
 
         info!("Cache hit: Redirecting {} to {}", token, link.long_url);
         return Redirect::permanent(&link.long_url).into_response();
     }
 
-    match get_short_link(&state.db, &token, &state.csv_links).await {
+    state.metrics.cache_total.with_label_values(&["miss"]).inc();
+
+    let lookup_timer = state.metrics.lookup_duration_seconds.start_timer();
+    let lookup_result = state.store.lookup(&token).await;
+    lookup_timer.observe_duration();
+
+    match lookup_result {
         Ok(Some(link)) => {
             let long_url = link.long_url.clone();
             let source = link.source;
-            
+
             // Store in cache for future requests
             state.link_cache.insert(token.clone(), link).await;
 
-            // Increment click count asynchronously only for database entries
-            if source == LinkSource::Database {
-                let db_clone = state.db.clone();
-                let token_clone = token.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = increment_click_count(&db_clone, &token_clone).await {
-                        warn!("Failed to increment click count for {}: {}", token_clone, e);
-                    }
-                });
-            }
+            state
+                .metrics
+                .redirects_total
+                .with_label_values(&[source.as_label(), "miss"])
+                .inc();
+
+            track_click(&state, &token, source, &headers, peer);
 
             info!("Cache miss: Redirecting {} to {}", token, long_url);
             Redirect::permanent(&long_url).into_response()
         }
         Ok(None) => {
+            state
+                .metrics
+                .redirects_total
+                .with_label_values(&["unknown", "not_found"])
+                .inc();
             warn!("Token not found: {}", token);
             (StatusCode::NOT_FOUND, "Short link not found").into_response()
         }
         Err(e) => {
-            warn!("Database error for token {}: {}", token, e);
+            state
+                .metrics
+                .redirects_total
+                .with_label_values(&["unknown", "error"])
+                .inc();
+            warn!("Store error for token {}: {}", token, e);
             (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
         }
     }
 }
 
+/// Fire off click bookkeeping on a background task so it never delays the redirect:
+/// bump the store's click counter, and (when Postgres-backed analytics are
+/// configured) append a row to `link_clicks` regardless of which store served the link.
+fn track_click(state: &AppState, token: &str, source: LinkSource, headers: &HeaderMap, peer: Peer) {
+    let store = state.store.clone();
+    let analytics_db = state.analytics_db.clone();
+    let token = token.to_string();
+    let referrer = headers
+        .get(axum::http::header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let user_agent = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let client_ip = analytics::client_ip(headers, peer, state.trusted_proxy);
+
+    tokio::spawn(async move {
+        if let Err(e) = store.record_click(&token, source).await {
+            warn!("Failed to record click for {}: {}", token, e);
+        }
+
+        if let Some(pool) = analytics_db {
+            if let Err(e) = analytics::record_click(&pool, &token, referrer, user_agent, client_ip).await {
+                warn!("Failed to record click analytics for {}: {}", token, e);
+            }
+        }
+    });
+}
+
 async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
@@ -232,60 +358,3 @@ fn read_csv_links() -> HashMap<String, String> {
     }
 }
 
-async fn get_short_link(pool: &PgPool, token: &str, csv_links: &HashMap<String, String>) -> anyhow::Result<Option<ShortLink>> {
-    // First, try to get the link from the database
-    let row = sqlx::query(
-        "SELECT id, token, long_url, created_at, click_count, is_active 
-         FROM short_links 
-         WHERE token = $1 AND is_active = true",
-    )
-    .bind(token)
-    .fetch_optional(pool)
-    .await?;
-
-    match row {
-        Some(row) => Ok(Some(ShortLink {
-            id: row.get("id"),
-            token: row.get("token"),
-            long_url: row.get("long_url"),
-            created_at: row.get("created_at"),
-            click_count: row.get("click_count"),
-            is_active: row.get("is_active"),
-            source: LinkSource::Database,
-        })),
-        None => {
-            // If not found in database, check pre-loaded CSV links
-            if let Some(long_url) = csv_links.get(token) {
-                info!("Found token {} in CSV links, redirecting to {}", token, long_url);
-                
-                // Create a ShortLink struct for CSV entries
-                // Note: id, created_at are placeholders since CSV entries don't have database records
-                Ok(Some(ShortLink {
-                    id: uuid::Uuid::nil(), // Use nil UUID to indicate this is not a real DB entry
-                    token: token.to_string(),
-                    long_url: long_url.clone(),
-                    created_at: chrono::DateTime::UNIX_EPOCH, // Placeholder timestamp
-                    click_count: 0, // CSV entries don't track clicks
-                    is_active: true,
-                    source: LinkSource::Csv,
-                }))
-            } else {
-                Ok(None)
-            }
-        }
-    }
-}
-
-async fn increment_click_count(pool: &PgPool, token: &str) -> anyhow::Result<()> {
-    sqlx::query(
-        "UPDATE short_links 
-         SET click_count = click_count + 1, updated_at = NOW() 
-         WHERE token = $1",
-    )
-    .bind(token)
-    .execute(pool)
-    .await?;
-
-
-    Ok(())
-}
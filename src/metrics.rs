@@ -0,0 +1,79 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use tracing::warn;
+
+use crate::AppState;
+
+/// Prometheus recorder for redirect traffic, cache effectiveness, and store health.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    pub redirects_total: IntCounterVec,
+    pub cache_total: IntCounterVec,
+    pub csv_links: IntGauge,
+    pub link_cache_entries: IntGauge,
+    pub lookup_duration_seconds: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> anyhow::Result<Self> {
+        let registry = Registry::new();
+
+        let redirects_total = IntCounterVec::new(
+            Opts::new("redirects_total", "Total redirect requests handled"),
+            &["source", "outcome"],
+        )?;
+
+        let cache_total = IntCounterVec::new(
+            Opts::new("link_cache_requests_total", "Lookups served by the in-memory link cache"),
+            &["result"],
+        )?;
+
+        let csv_links = IntGauge::new("csv_links", "Number of static redirects loaded from links.csv")?;
+
+        let link_cache_entries =
+            IntGauge::new("link_cache_entries", "Current number of entries in the link cache")?;
+
+        let lookup_duration_seconds = Histogram::with_opts(HistogramOpts::new(
+            "store_lookup_duration_seconds",
+            "Latency of LinkStore::lookup calls on a cache miss",
+        ))?;
+
+        registry.register(Box::new(redirects_total.clone()))?;
+        registry.register(Box::new(cache_total.clone()))?;
+        registry.register(Box::new(csv_links.clone()))?;
+        registry.register(Box::new(link_cache_entries.clone()))?;
+        registry.register(Box::new(lookup_duration_seconds.clone()))?;
+
+        Ok(Self {
+            registry,
+            redirects_total,
+            cache_total,
+            csv_links,
+            link_cache_entries,
+            lookup_duration_seconds,
+        })
+    }
+
+    fn render(&self) -> anyhow::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buf)?;
+        Ok(buf)
+    }
+}
+
+/// `GET /metrics` — Prometheus text-format scrape endpoint.
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    state
+        .metrics
+        .link_cache_entries
+        .set(state.link_cache.entry_count() as i64);
+
+    match state.metrics.render() {
+        Ok(body) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+        Err(e) => {
+            warn!("Failed to render metrics: {}", e);
+            (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "Failed to render metrics").into_response()
+        }
+    }
+}
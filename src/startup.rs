@@ -0,0 +1,97 @@
+use std::{os::unix::fs::PermissionsExt, path::Path, time::Duration};
+
+use axum::{
+    Router,
+    extract::connect_info::Connected,
+    serve::IncomingStream,
+};
+use rand::Rng;
+use sqlx::PgPool;
+use tokio::net::{TcpListener, UnixListener};
+use tracing::{info, warn};
+
+use crate::AppState;
+
+const MAX_CONNECT_ATTEMPTS: u32 = 8;
+const BASE_DELAY: Duration = Duration::from_millis(250);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Connect to Postgres, retrying with exponential backoff (plus jitter, capped at
+/// `MAX_DELAY`) instead of aborting on the first failure. This lets the server and
+/// database come up together under an orchestrator without racing into a crash loop.
+pub async fn connect_with_retry(database_url: &str) -> anyhow::Result<PgPool> {
+    for attempt in 1..=MAX_CONNECT_ATTEMPTS {
+        match PgPool::connect(database_url).await {
+            Ok(pool) => {
+                if attempt > 1 {
+                    info!("Connected to database on attempt {}/{}", attempt, MAX_CONNECT_ATTEMPTS);
+                }
+                return Ok(pool);
+            }
+            Err(e) if attempt < MAX_CONNECT_ATTEMPTS => {
+                let backoff = BASE_DELAY.saturating_mul(1u32 << (attempt - 1)).min(MAX_DELAY);
+                let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=backoff.as_millis() as u64 / 2 + 1));
+                let delay = backoff + jitter;
+                warn!(
+                    "Database connection attempt {}/{} failed: {}. Retrying in {:?}",
+                    attempt, MAX_CONNECT_ATTEMPTS, e, delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                warn!("Giving up on database connection after {} attempts: {}", MAX_CONNECT_ATTEMPTS, e);
+                return Err(e.into());
+            }
+        }
+    }
+
+    unreachable!("the loop above always returns on its final attempt")
+}
+
+/// Peer address for a redirect request, unified across TCP and Unix domain socket
+/// listeners so the same handler works regardless of how the server is bound.
+#[derive(Clone, Copy, Debug)]
+pub enum Peer {
+    Tcp(std::net::SocketAddr),
+    Unix,
+}
+
+impl Connected<IncomingStream<'_, TcpListener>> for Peer {
+    fn connect_info(stream: IncomingStream<'_, TcpListener>) -> Self {
+        match stream.io().peer_addr() {
+            Ok(addr) => Peer::Tcp(addr),
+            Err(_) => Peer::Unix,
+        }
+    }
+}
+
+impl Connected<IncomingStream<'_, UnixListener>> for Peer {
+    fn connect_info(_stream: IncomingStream<'_, UnixListener>) -> Self {
+        Peer::Unix
+    }
+}
+
+/// Bind `bind_address` and serve `app`. A `unix:/path/to/socket.sock` value binds a
+/// Unix domain socket instead of TCP — removing any stale socket file left behind
+/// by a previous run and granting read/write to the owning user and group, the way
+/// an nginx `upstream` expects to find it.
+pub async fn bind_and_serve(bind_address: &str, app: Router<AppState>) -> anyhow::Result<()> {
+    if let Some(path) = bind_address.strip_prefix("unix:") {
+        if Path::new(path).exists() {
+            std::fs::remove_file(path)?;
+        }
+
+        let listener = UnixListener::bind(path)?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o660))?;
+        info!("Server starting on unix:{}", path);
+
+        axum::serve(listener, app.into_make_service_with_connect_info::<Peer>()).await?;
+    } else {
+        let listener = TcpListener::bind(bind_address).await?;
+        info!("Server starting on {}", bind_address);
+
+        axum::serve(listener, app.into_make_service_with_connect_info::<Peer>()).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{LinkSource, ShortLink, store::LinkStore};
+
+/// Chains an immutable, read-only backend (the CSV map) behind a primary,
+/// writable backend (Postgres, Redis, or in-memory). Lookups check the primary
+/// first and fall back to the secondary; creates always go to the primary, and
+/// clicks go to whichever backend the link's `source` says served it.
+pub struct CompositeStore {
+    primary: Arc<dyn LinkStore>,
+    fallback: Arc<dyn LinkStore>,
+}
+
+impl CompositeStore {
+    pub fn new(primary: Arc<dyn LinkStore>, fallback: Arc<dyn LinkStore>) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+#[async_trait]
+impl LinkStore for CompositeStore {
+    async fn lookup(&self, token: &str) -> anyhow::Result<Option<ShortLink>> {
+        match self.primary.lookup(token).await? {
+            Some(link) => Ok(Some(link)),
+            None => self.fallback.lookup(token).await,
+        }
+    }
+
+    async fn record_click(&self, token: &str, source: LinkSource) -> anyhow::Result<()> {
+        // Route the click to whichever backend actually served the lookup this
+        // link came from — blindly hitting the primary regardless of `source`
+        // used to mean a CSV-sourced click against a Redis primary would `INCR` a
+        // stray counter key for a token Redis never stored.
+        match source {
+            LinkSource::Csv => self.fallback.record_click(token, source).await,
+            _ => self.primary.record_click(token, source).await,
+        }
+    }
+
+    async fn create(&self, long_url: &str) -> anyhow::Result<ShortLink> {
+        self.primary.create(long_url).await
+    }
+}
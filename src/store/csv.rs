@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+
+use crate::{LinkSource, ShortLink, store::LinkStore};
+
+/// Read-only `LinkStore` backed by the `links.csv` map. Immutable from the store's
+/// point of view (`create` / `record_click` are errors/no-ops), but the map itself
+/// is hot-reloadable: it lives behind an `ArcSwap` so a background watcher can swap
+/// in a freshly parsed snapshot without restarting the server.
+pub struct CsvStore {
+    links: std::sync::Arc<ArcSwap<HashMap<String, String>>>,
+}
+
+impl CsvStore {
+    pub fn new(links: std::sync::Arc<ArcSwap<HashMap<String, String>>>) -> Self {
+        Self { links }
+    }
+}
+
+#[async_trait]
+impl LinkStore for CsvStore {
+    async fn lookup(&self, token: &str) -> anyhow::Result<Option<ShortLink>> {
+        Ok(self.links.load().get(token).map(|long_url| ShortLink {
+            id: uuid::Uuid::nil(),
+            token: token.to_string(),
+            long_url: long_url.clone(),
+            created_at: chrono::DateTime::UNIX_EPOCH,
+            click_count: 0,
+            is_active: true,
+            source: LinkSource::Csv,
+        }))
+    }
+
+    async fn record_click(&self, _token: &str, _source: LinkSource) -> anyhow::Result<()> {
+        // CSV entries don't track clicks; matches the old behavior where
+        // `increment_click_count` was only ever called for database entries.
+        Ok(())
+    }
+
+    async fn create(&self, _long_url: &str) -> anyhow::Result<ShortLink> {
+        Err(anyhow::anyhow!("the CSV store is read-only"))
+    }
+}
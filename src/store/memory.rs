@@ -0,0 +1,60 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicI64, Ordering},
+};
+
+use async_trait::async_trait;
+use sqids::Sqids;
+use tokio::sync::RwLock;
+
+use crate::{LinkSource, ShortLink, store::LinkStore, tokens::token_for_seq};
+
+/// A purely in-memory `LinkStore`. Nothing here survives a restart; useful for
+/// tests and for running the redirector without any external dependency.
+pub struct MemoryStore {
+    links: RwLock<std::collections::HashMap<String, ShortLink>>,
+    next_seq: AtomicI64,
+    sqids: Arc<Sqids>,
+}
+
+impl MemoryStore {
+    pub fn new(sqids: Arc<Sqids>) -> Self {
+        Self {
+            links: RwLock::new(std::collections::HashMap::new()),
+            next_seq: AtomicI64::new(1),
+            sqids,
+        }
+    }
+}
+
+#[async_trait]
+impl LinkStore for MemoryStore {
+    async fn lookup(&self, token: &str) -> anyhow::Result<Option<ShortLink>> {
+        Ok(self.links.read().await.get(token).cloned())
+    }
+
+    async fn record_click(&self, token: &str, _source: LinkSource) -> anyhow::Result<()> {
+        if let Some(link) = self.links.write().await.get_mut(token) {
+            link.click_count += 1;
+        }
+        Ok(())
+    }
+
+    async fn create(&self, long_url: &str) -> anyhow::Result<ShortLink> {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        let token = token_for_seq(&self.sqids, seq);
+
+        let link = ShortLink {
+            id: uuid::Uuid::new_v4(),
+            token: token.clone(),
+            long_url: long_url.to_string(),
+            created_at: chrono::Utc::now(),
+            click_count: 0,
+            is_active: true,
+            source: LinkSource::Database,
+        };
+
+        self.links.write().await.insert(token, link.clone());
+        Ok(link)
+    }
+}
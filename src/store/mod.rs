@@ -0,0 +1,28 @@
+pub mod composite;
+pub mod csv;
+pub mod memory;
+pub mod postgres;
+pub mod redis;
+
+use async_trait::async_trait;
+
+use crate::{LinkSource, ShortLink};
+
+/// Storage backend for short links, abstracting over where links actually live
+/// (Postgres, Redis, an in-memory map, or a read-only CSV fallback) so the HTTP
+/// layer never has to special-case a particular backend.
+#[async_trait]
+pub trait LinkStore: Send + Sync {
+    /// Look up an active link by its token.
+    async fn lookup(&self, token: &str) -> anyhow::Result<Option<ShortLink>>;
+
+    /// Record a redirect against `token` (bump its click counter). `source` is the
+    /// `LinkSource` the original lookup returned, so a composite store can route
+    /// the click to whichever backend actually owns the token instead of
+    /// guessing. A no-op for read-only backends such as the CSV fallback.
+    async fn record_click(&self, token: &str, source: LinkSource) -> anyhow::Result<()>;
+
+    /// Create a new link for `long_url`, deriving its token, and return the
+    /// stored record. Errors for read-only backends.
+    async fn create(&self, long_url: &str) -> anyhow::Result<ShortLink>;
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqids::Sqids;
+use sqlx::{PgPool, Row};
+
+use crate::{LinkSource, ShortLink, store::LinkStore, tokens::token_for_seq};
+
+/// The original, database-backed `LinkStore`: this is the behavior that used to
+/// live directly in `get_short_link` / `increment_click_count` before storage
+/// became pluggable.
+pub struct PostgresStore {
+    pool: PgPool,
+    sqids: Arc<Sqids>,
+}
+
+impl PostgresStore {
+    pub fn new(pool: PgPool, sqids: Arc<Sqids>) -> Self {
+        Self { pool, sqids }
+    }
+}
+
+#[async_trait]
+impl LinkStore for PostgresStore {
+    async fn lookup(&self, token: &str) -> anyhow::Result<Option<ShortLink>> {
+        let row = sqlx::query(
+            "SELECT id, token, long_url, created_at, click_count, is_active
+             FROM short_links
+             WHERE token = $1 AND is_active = true",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| ShortLink {
+            id: row.get("id"),
+            token: row.get("token"),
+            long_url: row.get("long_url"),
+            created_at: row.get("created_at"),
+            click_count: row.get("click_count"),
+            is_active: row.get("is_active"),
+            source: LinkSource::Database,
+        }))
+    }
+
+    async fn record_click(&self, token: &str, _source: LinkSource) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE short_links
+             SET click_count = click_count + 1, updated_at = NOW()
+             WHERE token = $1",
+        )
+        .bind(token)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn create(&self, long_url: &str) -> anyhow::Result<ShortLink> {
+        // Derive the token from the sequence value up front so the row can be
+        // inserted with its token already set, rather than inserting first and
+        // patching the token in afterwards — that left a window where a dropped
+        // connection between the two statements stranded a permanent `token = NULL`
+        // row.
+        let seq: i64 = sqlx::query_scalar("SELECT nextval('short_links_seq_seq')")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let token = token_for_seq(&self.sqids, seq);
+
+        let row = sqlx::query(
+            "INSERT INTO short_links (seq, token, long_url, is_active) VALUES ($1, $2, $3, true)
+             RETURNING id, created_at",
+        )
+        .bind(seq)
+        .bind(&token)
+        .bind(long_url)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let id: uuid::Uuid = row.get("id");
+        let created_at = row.get("created_at");
+
+        Ok(ShortLink {
+            id,
+            token,
+            long_url: long_url.to_string(),
+            created_at,
+            click_count: 0,
+            is_active: true,
+            source: LinkSource::Database,
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use redis::{AsyncCommands, aio::ConnectionManager};
+use sqids::Sqids;
+
+use crate::{LinkSource, ShortLink, store::LinkStore, tokens::token_for_seq};
+
+/// Redis-backed `LinkStore`: `token -> long_url` in a plain string key, click
+/// counts in a companion `INCR` counter. Ideal for high-traffic redirects where
+/// a round trip to Postgres per click would be wasteful.
+pub struct RedisStore {
+    conn: ConnectionManager,
+    sqids: Arc<Sqids>,
+}
+
+impl RedisStore {
+    pub async fn connect(redis_url: &str, sqids: Arc<Sqids>) -> anyhow::Result<Self> {
+        let client = redis::Client::open(redis_url)?;
+        let conn = ConnectionManager::new(client).await?;
+        Ok(Self { conn, sqids })
+    }
+
+    fn url_key(token: &str) -> String {
+        format!("link:{token}:url")
+    }
+
+    fn clicks_key(token: &str) -> String {
+        format!("link:{token}:clicks")
+    }
+}
+
+#[async_trait]
+impl LinkStore for RedisStore {
+    async fn lookup(&self, token: &str) -> anyhow::Result<Option<ShortLink>> {
+        let mut conn = self.conn.clone();
+        let long_url: Option<String> = conn.get(Self::url_key(token)).await?;
+
+        let Some(long_url) = long_url else {
+            return Ok(None);
+        };
+
+        let click_count: i64 = conn.get(Self::clicks_key(token)).await.unwrap_or(0);
+
+        Ok(Some(ShortLink {
+            id: uuid::Uuid::nil(),
+            token: token.to_string(),
+            long_url,
+            created_at: chrono::Utc::now(),
+            click_count,
+            is_active: true,
+            source: LinkSource::Database,
+        }))
+    }
+
+    async fn record_click(&self, token: &str, _source: LinkSource) -> anyhow::Result<()> {
+        let mut conn = self.conn.clone();
+        conn.incr(Self::clicks_key(token), 1).await?;
+        Ok(())
+    }
+
+    async fn create(&self, long_url: &str) -> anyhow::Result<ShortLink> {
+        let mut conn = self.conn.clone();
+        let seq: i64 = conn.incr("link:seq", 1).await?;
+        let token = token_for_seq(&self.sqids, seq);
+
+        conn.set::<_, _, ()>(Self::url_key(&token), long_url).await?;
+
+        Ok(ShortLink {
+            id: uuid::Uuid::nil(),
+            token,
+            long_url: long_url.to_string(),
+            created_at: chrono::Utc::now(),
+            click_count: 0,
+            is_active: true,
+            source: LinkSource::Database,
+        })
+    }
+}
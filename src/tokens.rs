@@ -0,0 +1,91 @@
+use sqids::{DEFAULT_ALPHABET, Sqids};
+
+/// Substrings we never want to hand out in a public short link.
+const BLOCKLIST: &[&str] = &["admin", "root", "test", "fuck", "shit"];
+
+/// Build the `Sqids` encoder used to derive tokens from a link's sequential id.
+///
+/// The encoding only stays collision-free across restarts if the alphabet is
+/// fixed, so we never randomize it ourselves: without `SHORTLINK_ALPHABET` we
+/// fall back to the stock Sqids alphabet verbatim. Operators who want tokens
+/// that aren't guessable from the public Sqids alphabet list should set
+/// `SHORTLINK_ALPHABET` to their own fixed, shuffled alphabet.
+pub fn build_sqids(alphabet: Option<String>, min_length: u8) -> anyhow::Result<Sqids> {
+    let alphabet: Vec<char> = match alphabet {
+        Some(alphabet) => alphabet.chars().collect(),
+        None => DEFAULT_ALPHABET.chars().collect(),
+    };
+
+    Sqids::builder()
+        .alphabet(alphabet)
+        .min_length(min_length)
+        .build()
+        .map_err(|e| anyhow::anyhow!("failed to build sqids encoder: {e}"))
+}
+
+/// Derive a token for `seq` (the row's `BIGSERIAL` id), regenerating with a bumped
+/// salt value until the result clears [`BLOCKLIST`].
+///
+/// Sqids is fully reversible, so encoding `[seq]` alone is guaranteed unique for a
+/// monotonically increasing id; the bump is only ever needed to dodge the blocklist,
+/// never to avoid a collision.
+pub fn token_for_seq(sqids: &Sqids, seq: i64) -> String {
+    token_for_seq_against(sqids, seq, BLOCKLIST)
+}
+
+/// Shared implementation taking the blocklist as a parameter so tests can force a
+/// hit deterministically without depending on `Sqids`'s actual alphabet.
+fn token_for_seq_against(sqids: &Sqids, seq: i64, blocklist: &[&str]) -> String {
+    let seq = seq as u64;
+    let mut bump: u64 = 0;
+
+    loop {
+        let ids: &[u64] = if bump == 0 { &[seq] } else { &[seq, bump] };
+        let candidate = sqids
+            .encode(ids)
+            .expect("sqids encode should not fail for in-range ids");
+
+        let lower = candidate.to_lowercase();
+        if !blocklist.iter().any(|banned| lower.contains(&banned.to_lowercase())) {
+            return candidate;
+        }
+
+        bump += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seq_is_deterministic_across_calls() {
+        let sqids = build_sqids(None, 0).unwrap();
+        assert_eq!(token_for_seq(&sqids, 42), token_for_seq(&sqids, 42));
+    }
+
+    #[test]
+    fn different_seqs_produce_different_tokens() {
+        let sqids = build_sqids(None, 0).unwrap();
+        assert_ne!(token_for_seq(&sqids, 1), token_for_seq(&sqids, 2));
+    }
+
+    #[test]
+    fn regenerates_when_the_bare_encoding_hits_the_blocklist() {
+        let sqids = build_sqids(None, 0).unwrap();
+        let bare = sqids.encode(&[7]).unwrap();
+
+        // Block the exact bare encoding so bump=0 is guaranteed to be rejected.
+        let blocklist = [bare.as_str()];
+        let token = token_for_seq_against(&sqids, 7, &blocklist);
+
+        assert_ne!(token, bare);
+        assert!(!token.to_lowercase().contains(&bare.to_lowercase()));
+    }
+
+    #[test]
+    fn default_blocklist_contains_known_bad_words() {
+        assert!(BLOCKLIST.iter().any(|w| *w == "admin"));
+        assert!(BLOCKLIST.iter().any(|w| *w == "test"));
+    }
+}